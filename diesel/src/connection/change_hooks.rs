@@ -0,0 +1,37 @@
+/// The kind of row-level change that triggered an
+/// [`ConnectionChangeHooks::update_hook`] callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Operation {
+    /// A row was inserted
+    Insert,
+    /// A row was updated
+    Update,
+    /// A row was deleted
+    Delete,
+}
+
+/// Backend-neutral hooks into a connection's change/transaction lifecycle.
+///
+/// Not every backend can support every hook (and some, like `commit_hook`,
+/// are meaningful only where the backend itself drives the transaction
+/// rather than the server), so each method defaults to doing nothing;
+/// backends override the ones their underlying driver exposes.
+///
+/// Re-registering a hook replaces whatever was previously registered for
+/// it; passing `None` clears it. All hooks are cleared automatically when
+/// the connection is dropped.
+pub trait ConnectionChangeHooks {
+    /// Registers a callback invoked whenever a row is inserted, updated, or
+    /// deleted, receiving the [`Operation`], the name of the table that
+    /// changed, and the `rowid` of the affected row.
+    fn update_hook(&mut self, _callback: Option<Box<dyn FnMut(Operation, &str, i64) + Send>>) {}
+
+    /// Registers a callback invoked immediately before a transaction
+    /// commits. Returning `true` aborts the commit (turning it into a
+    /// rollback).
+    fn commit_hook(&mut self, _callback: Option<Box<dyn FnMut() -> bool + Send>>) {}
+
+    /// Registers a callback invoked whenever a transaction rolls back.
+    fn rollback_hook(&mut self, _callback: Option<Box<dyn FnMut() + Send>>) {}
+}
@@ -0,0 +1,53 @@
+//! Type aliases for the return type of methods in this crate.
+//!
+//! This module only contains the aliases that are currently relevant to the
+//! vendored subset of the crate present in this checkout; the full version
+//! re-exports every query-building helper diesel provides.
+
+use crate::expression::grouped::Grouped;
+use crate::expression::operators;
+
+/// The return type of [`concat()`](crate::TextExpressionMethods::concat)
+pub type Concat<Lhs, Rhs> = Grouped<
+    operators::Concat<
+        Lhs,
+        <Rhs as crate::expression::AsExpression<<Lhs as crate::expression::Expression>::SqlType>>::Expression,
+    >,
+>;
+
+/// The return type of [`like()`](crate::TextExpressionMethods::like)
+pub type Like<Lhs, Rhs> = Grouped<
+    operators::Like<
+        Lhs,
+        <Rhs as crate::expression::AsExpression<<Lhs as crate::expression::Expression>::SqlType>>::Expression,
+    >,
+>;
+
+/// The return type of [`not_like()`](crate::TextExpressionMethods::not_like)
+pub type NotLike<Lhs, Rhs> = Grouped<
+    operators::NotLike<
+        Lhs,
+        <Rhs as crate::expression::AsExpression<<Lhs as crate::expression::Expression>::SqlType>>::Expression,
+    >,
+>;
+
+/// The return type of [`escape()`](crate::expression_methods::EscapeExpressionMethods::escape)
+pub type Escape<Like> = Grouped<operators::LikeEscape<Like>>;
+
+/// The return type of
+/// [`glob()`](crate::expression_methods::SqliteTextExpressionMethods::glob)
+pub type Glob<Lhs, Rhs> = Grouped<
+    operators::Glob<
+        Lhs,
+        <Rhs as crate::expression::AsExpression<<Lhs as crate::expression::Expression>::SqlType>>::Expression,
+    >,
+>;
+
+/// The return type of
+/// [`not_glob()`](crate::expression_methods::SqliteTextExpressionMethods::not_glob)
+pub type NotGlob<Lhs, Rhs> = Grouped<
+    operators::NotGlob<
+        Lhs,
+        <Rhs as crate::expression::AsExpression<<Lhs as crate::expression::Expression>::SqlType>>::Expression,
+    >,
+>;
@@ -0,0 +1,36 @@
+use crate::backend::Backend;
+use crate::expression::{Expression, ValidGrouping};
+use crate::query_builder::{AstPass, QueryFragment, QueryId};
+use crate::result::QueryResult;
+
+/// Wraps an expression in parentheses when it is written to SQL.
+///
+/// This is used by binary operators (e.g. `Concat`, `Like`) so that the
+/// operator they produce is unambiguous regardless of where it ends up
+/// nested in a larger expression tree.
+#[derive(Debug, Copy, Clone, QueryId)]
+pub struct Grouped<T>(pub T);
+
+impl<T: Expression> Expression for Grouped<T> {
+    type SqlType = T::SqlType;
+}
+
+impl<T, GB> ValidGrouping<GB> for Grouped<T>
+where
+    T: ValidGrouping<GB>,
+{
+    type IsAggregate = T::IsAggregate;
+}
+
+impl<T, DB> QueryFragment<DB> for Grouped<T>
+where
+    DB: Backend,
+    T: QueryFragment<DB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        out.push_sql("(");
+        self.0.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use crate::backend::Backend;
+use crate::expression::{Expression, ValidGrouping};
+use crate::query_builder::{AstPass, QueryFragment, QueryId};
+use crate::result::QueryResult;
+use crate::sql_types::{Bool, Text};
+
+macro_rules! infix_predicate {
+    ($name:ident, $sql:expr) => {
+        #[derive(Debug, Copy, Clone, QueryId)]
+        #[doc(hidden)]
+        pub struct $name<Lhs, Rhs> {
+            pub(crate) lhs: Lhs,
+            pub(crate) rhs: Rhs,
+        }
+
+        impl<Lhs, Rhs> $name<Lhs, Rhs> {
+            pub fn new(lhs: Lhs, rhs: Rhs) -> Self {
+                $name { lhs, rhs }
+            }
+        }
+
+        impl<Lhs, Rhs> Expression for $name<Lhs, Rhs>
+        where
+            Lhs: Expression,
+            Rhs: Expression,
+        {
+            type SqlType = Bool;
+        }
+
+        impl<Lhs, Rhs, GB> ValidGrouping<GB> for $name<Lhs, Rhs>
+        where
+            Lhs: ValidGrouping<GB>,
+            Rhs: ValidGrouping<GB>,
+        {
+            type IsAggregate = Lhs::IsAggregate;
+        }
+
+        impl<Lhs, Rhs, DB> QueryFragment<DB> for $name<Lhs, Rhs>
+        where
+            DB: Backend,
+            Lhs: QueryFragment<DB>,
+            Rhs: QueryFragment<DB>,
+        {
+            fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+                self.lhs.walk_ast(out.reborrow())?;
+                out.push_sql($sql);
+                self.rhs.walk_ast(out.reborrow())?;
+                Ok(())
+            }
+        }
+    };
+}
+
+#[derive(Debug, Copy, Clone, QueryId)]
+#[doc(hidden)]
+pub struct Concat<Lhs, Rhs> {
+    pub(crate) lhs: Lhs,
+    pub(crate) rhs: Rhs,
+}
+
+impl<Lhs, Rhs> Concat<Lhs, Rhs> {
+    pub fn new(lhs: Lhs, rhs: Rhs) -> Self {
+        Concat { lhs, rhs }
+    }
+}
+
+impl<Lhs, Rhs> Expression for Concat<Lhs, Rhs>
+where
+    Lhs: Expression,
+    Rhs: Expression,
+{
+    type SqlType = Lhs::SqlType;
+}
+
+impl<Lhs, Rhs, GB> ValidGrouping<GB> for Concat<Lhs, Rhs>
+where
+    Lhs: ValidGrouping<GB>,
+    Rhs: ValidGrouping<GB>,
+{
+    type IsAggregate = Lhs::IsAggregate;
+}
+
+impl<Lhs, Rhs, DB> QueryFragment<DB> for Concat<Lhs, Rhs>
+where
+    DB: Backend,
+    Lhs: QueryFragment<DB>,
+    Rhs: QueryFragment<DB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        self.lhs.walk_ast(out.reborrow())?;
+        out.push_sql(" || ");
+        self.rhs.walk_ast(out.reborrow())?;
+        Ok(())
+    }
+}
+
+infix_predicate!(Like, " LIKE ");
+infix_predicate!(NotLike, " NOT LIKE ");
+
+/// `lhs LIKE rhs ESCAPE escape` / `lhs NOT LIKE rhs ESCAPE escape`
+///
+/// `Like` is either [`Like`] or [`NotLike`]; `escape` is always bound as
+/// a single-character `Text` value, which keeps the escape character
+/// from being anything SQL would interpret as more than one character.
+#[derive(Debug, Copy, Clone, QueryId)]
+#[doc(hidden)]
+pub struct LikeEscape<Like> {
+    pub(crate) like: Like,
+    pub(crate) escape: EscapeChar,
+}
+
+impl<Like> LikeEscape<Like> {
+    pub fn new(like: Like, escape_char: char) -> Self {
+        LikeEscape {
+            like,
+            escape: EscapeChar(escape_char),
+        }
+    }
+}
+
+impl<Like: Expression> Expression for LikeEscape<Like> {
+    type SqlType = Bool;
+}
+
+impl<Like, GB> ValidGrouping<GB> for LikeEscape<Like>
+where
+    Like: ValidGrouping<GB>,
+{
+    type IsAggregate = Like::IsAggregate;
+}
+
+impl<Like, DB> QueryFragment<DB> for LikeEscape<Like>
+where
+    DB: Backend,
+    Like: QueryFragment<DB>,
+    EscapeChar: QueryFragment<DB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        self.like.walk_ast(out.reborrow())?;
+        out.push_sql(" ESCAPE ");
+        self.escape.walk_ast(out.reborrow())?;
+        Ok(())
+    }
+}
+
+infix_predicate!(Glob, " GLOB ");
+infix_predicate!(NotGlob, " NOT GLOB ");
+
+/// A single-character bind parameter of SQL type `Text`, used as the
+/// operand of `LikeEscape`. Wrapping a plain `char` (rather than a
+/// `String`) is what guarantees the escape sequence is exactly one
+/// character wide.
+#[derive(Debug, Copy, Clone, QueryId)]
+#[doc(hidden)]
+pub struct EscapeChar(pub(crate) char);
+
+impl Expression for EscapeChar {
+    type SqlType = Text;
+}
+
+impl<GB> ValidGrouping<GB> for EscapeChar {
+    type IsAggregate = crate::expression::is_aggregate::Never;
+}
+
+impl<DB> QueryFragment<DB> for EscapeChar
+where
+    DB: Backend,
+    String: crate::serialize::ToSql<Text, DB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        out.push_bind_param::<Text, _>(&self.0.to_string())
+    }
+}
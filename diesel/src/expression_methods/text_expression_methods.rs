@@ -1,6 +1,6 @@
 use crate::dsl;
 use crate::expression::grouped::Grouped;
-use crate::expression::operators::{Concat, Like, NotLike};
+use crate::expression::operators::{Concat, Like, LikeEscape, NotLike};
 use crate::expression::{AsExpression, Expression};
 use crate::sql_types::{Nullable, SqlType, Text};
 
@@ -152,3 +152,61 @@ where
     T::SqlType: TextOrNullableText,
 {
 }
+
+/// Adds an `ESCAPE` clause to a `LIKE`/`NOT LIKE` expression
+///
+/// This is only implemented for the expressions produced by
+/// [`like()`](TextExpressionMethods::like) and
+/// [`not_like()`](TextExpressionMethods::not_like), since `ESCAPE` is only
+/// meaningful as part of a pattern match.
+pub trait EscapeExpressionMethods: Expression + Sized {
+    /// The type returned by [`escape`](EscapeExpressionMethods::escape)
+    type Output;
+
+    /// Specifies the character that escapes `%` and `_` in the pattern
+    /// passed to `like`/`not_like`, so that they can be matched literally.
+    ///
+    /// The escape character is always exactly one character wide, which is
+    /// enforced by taking a `char` rather than a `&str`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # include!("../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     use schema::users::dsl::*;
+    /// #     let mut connection = establish_connection();
+    /// #
+    /// let starts_with_percent = users
+    ///     .select(name)
+    ///     .filter(name.like("\\%%").escape('\\'))
+    ///     .load::<String>(&mut connection)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    fn escape(self, character: char) -> Self::Output;
+}
+
+impl<L, R> EscapeExpressionMethods for Grouped<Like<L, R>> {
+    type Output = dsl::Escape<Like<L, R>>;
+
+    fn escape(self, character: char) -> Self::Output {
+        // `self.0` is the bare (ungrouped) `Like`, so `LikeEscape` renders
+        // `ESCAPE` directly after the `LIKE` pattern, not after a
+        // parenthesized boolean expression.
+        Grouped(LikeEscape::new(self.0, character))
+    }
+}
+
+impl<L, R> EscapeExpressionMethods for Grouped<NotLike<L, R>> {
+    type Output = dsl::Escape<NotLike<L, R>>;
+
+    fn escape(self, character: char) -> Self::Output {
+        Grouped(LikeEscape::new(self.0, character))
+    }
+}
@@ -0,0 +1,220 @@
+use crate::backend::Backend;
+use crate::query_builder::{AstPass, QueryFragment, QueryId};
+use crate::query_source::Table;
+use crate::result::QueryResult;
+
+/// No `FOR ...` clause has been specified
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct NoLockingClause;
+
+impl<DB: Backend> QueryFragment<DB> for NoLockingClause {
+    fn walk_ast<'b>(&'b self, _out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        Ok(())
+    }
+}
+
+/// `FOR UPDATE`
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct ForUpdate;
+
+/// `FOR NO KEY UPDATE`
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct ForNoKeyUpdate;
+
+/// `FOR SHARE`
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct ForShare;
+
+/// `FOR KEY SHARE`
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct ForKeyShare;
+
+/// No `OF ...` clause has been specified; the lock applies to every
+/// relation referenced by the query.
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct NoOfClause;
+
+impl<DB: Backend> QueryFragment<DB> for NoOfClause {
+    fn walk_ast<'b>(&'b self, _out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        Ok(())
+    }
+}
+
+/// `OF <table> [, <table>]*`, restricting a row lock to the named relations
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct OfClause<Of>(pub(crate) Of);
+
+impl<DB, T> QueryFragment<DB> for OfClause<T>
+where
+    DB: Backend,
+    T: Table,
+    T::FromClause: QueryFragment<DB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        out.push_sql(" OF ");
+        self.0.from_clause().walk_ast(out.reborrow())
+    }
+}
+
+/// Types that can be passed to [`.of()`](crate::query_dsl::LockingClauseDsl::of):
+/// a single table, or a tuple of tables.
+///
+/// Implemented for `T: Table` and for 2- and 3-tuples of tables, matching
+/// the `OfClause<T>` and `OfClause<(T1, T2, ...)>` impls below.
+pub trait AppearsInOfClause {}
+
+impl<T: Table> AppearsInOfClause for T {}
+
+macro_rules! appears_in_of_clause_for_tuple {
+    ($($T:ident),+) => {
+        impl<$($T: Table),+> AppearsInOfClause for ($($T,)+) {}
+    };
+}
+
+appears_in_of_clause_for_tuple!(T1, T2);
+appears_in_of_clause_for_tuple!(T1, T2, T3);
+
+macro_rules! of_clause_for_tuple {
+    ($($T:ident),+) => {
+        impl<DB, $($T),+> QueryFragment<DB> for OfClause<($($T,)+)>
+        where
+            DB: Backend,
+            $($T: Table, $T::FromClause: QueryFragment<DB>,)+
+        {
+            fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+                out.push_sql(" OF ");
+                let ($(ref $T,)+) = self.0;
+                let mut first = true;
+                $(
+                    if !first {
+                        out.push_sql(", ");
+                    }
+                    first = false;
+                    $T.from_clause().walk_ast(out.reborrow())?;
+                )+
+                Ok(())
+            }
+        }
+    };
+}
+
+of_clause_for_tuple!(T1, T2);
+of_clause_for_tuple!(T1, T2, T3);
+
+/// No row-lock modifier (`SKIP LOCKED` / `NOWAIT`) has been specified
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct NoModifier;
+
+impl<DB: Backend> QueryFragment<DB> for NoModifier {
+    fn walk_ast<'b>(&'b self, _out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        Ok(())
+    }
+}
+
+/// `SKIP LOCKED`
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct SkipLocked;
+
+/// `NOWAIT`
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct NoWait;
+
+/// The `FOR ... [OF ...] [SKIP LOCKED | NOWAIT]` clause of a `SELECT`
+/// statement.
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct LockingClause<Lock, Of, Modifier> {
+    lock: Lock,
+    of: Of,
+    modifier: Modifier,
+}
+
+impl LockingClause<NoLockingClause, NoOfClause, NoModifier> {
+    pub(crate) fn new() -> Self {
+        LockingClause {
+            lock: NoLockingClause,
+            of: NoOfClause,
+            modifier: NoModifier,
+        }
+    }
+}
+
+impl<Lock, Of, Modifier> LockingClause<Lock, Of, Modifier> {
+    pub(crate) fn with_lock<Lock2>(self, lock: Lock2) -> LockingClause<Lock2, Of, Modifier> {
+        LockingClause {
+            lock,
+            of: self.of,
+            modifier: self.modifier,
+        }
+    }
+
+    pub(crate) fn with_of<Of2>(self, of: Of2) -> LockingClause<Lock, Of2, Modifier> {
+        LockingClause {
+            lock: self.lock,
+            of,
+            modifier: self.modifier,
+        }
+    }
+
+    pub(crate) fn with_modifier<Modifier2>(
+        self,
+        modifier: Modifier2,
+    ) -> LockingClause<Lock, Of, Modifier2> {
+        LockingClause {
+            lock: self.lock,
+            of: self.of,
+            modifier,
+        }
+    }
+}
+
+macro_rules! lock_mode {
+    ($ty:ty, $sql:expr) => {
+        impl<DB, Of, Modifier> QueryFragment<DB> for LockingClause<$ty, Of, Modifier>
+        where
+            DB: Backend,
+            Of: QueryFragment<DB>,
+            Modifier: QueryFragment<DB>,
+        {
+            fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+                out.push_sql($sql);
+                self.of.walk_ast(out.reborrow())?;
+                self.modifier.walk_ast(out.reborrow())?;
+                Ok(())
+            }
+        }
+    };
+}
+
+lock_mode!(ForUpdate, " FOR UPDATE");
+lock_mode!(ForNoKeyUpdate, " FOR NO KEY UPDATE");
+lock_mode!(ForShare, " FOR SHARE");
+lock_mode!(ForKeyShare, " FOR KEY SHARE");
+
+impl<DB, Of, Modifier> QueryFragment<DB> for LockingClause<NoLockingClause, Of, Modifier>
+where
+    DB: Backend,
+{
+    fn walk_ast<'b>(&'b self, _out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        Ok(())
+    }
+}
+
+impl<DB> QueryFragment<DB> for SkipLocked
+where
+    DB: Backend,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        out.push_sql(" SKIP LOCKED");
+        Ok(())
+    }
+}
+
+impl<DB> QueryFragment<DB> for NoWait
+where
+    DB: Backend,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        out.push_sql(" NOWAIT");
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::query_builder::locking_clause::*;
+
+/// The `.for_update()`, `.for_no_key_update()`, `.for_share()` and
+/// `.for_key_share()` methods
+///
+/// These set the row-lock mode of a `SELECT` statement. The SQL for every
+/// lock mode is rendered for any `Backend`; nothing here restricts a lock
+/// mode to the backends that accept it.
+pub trait LockingDsl<Lock> {
+    /// The type returned by these methods
+    type Output;
+
+    /// See the trait documentation
+    fn with_lock(self, lock: Lock) -> Self::Output;
+}
+
+/// The `.skip_locked()` and `.no_wait()` methods
+///
+/// These further qualify a row lock acquired via [`LockingDsl`], and can
+/// only be called once a lock mode has already been selected.
+pub trait ModifyLockDsl<Modifier> {
+    /// The type returned by these methods
+    type Output;
+
+    /// See the trait documentation
+    fn modify_lock(self, modifier: Modifier) -> Self::Output;
+}
+
+/// The `.of(table)` method
+///
+/// Restricts a row lock to the given relation(s), appending `OF <table>
+/// [, <table>]*` to the locking clause. Only meaningful once a lock mode
+/// has been selected. The `OF` clause is rendered for any `Backend`; it is
+/// not restricted to backends that support targeted row locks.
+pub trait OfDsl<Of> {
+    /// The type returned by `.of`
+    type Output;
+
+    /// See the trait documentation
+    fn of(self, of: Of) -> Self::Output;
+}
+
+macro_rules! lock_method {
+    ($method_name:ident, $lock:ty) => {
+        /// See [`LockingDsl`]
+        fn $method_name(self) -> <Self as LockingDsl<$lock>>::Output
+        where
+            Self: Sized + LockingDsl<$lock>,
+        {
+            self.with_lock($lock)
+        }
+    };
+}
+
+/// Methods to lock rows returned by a query, and to control how that lock
+/// is acquired.
+pub trait LockingClauseDsl: Sized {
+    lock_method!(for_update, ForUpdate);
+    lock_method!(for_no_key_update, ForNoKeyUpdate);
+    lock_method!(for_share, ForShare);
+    lock_method!(for_key_share, ForKeyShare);
+
+    /// See [`ModifyLockDsl`]
+    fn skip_locked(self) -> <Self as ModifyLockDsl<SkipLocked>>::Output
+    where
+        Self: ModifyLockDsl<SkipLocked>,
+    {
+        self.modify_lock(SkipLocked)
+    }
+
+    /// See [`ModifyLockDsl`]
+    fn no_wait(self) -> <Self as ModifyLockDsl<NoWait>>::Output
+    where
+        Self: ModifyLockDsl<NoWait>,
+    {
+        self.modify_lock(NoWait)
+    }
+
+    /// See [`OfDsl`]
+    fn of<Of>(self, of: Of) -> <Self as OfDsl<Of>>::Output
+    where
+        Self: OfDsl<Of>,
+        Of: AppearsInOfClause,
+    {
+        OfDsl::of(self, of)
+    }
+}
+
+impl<T> LockingClauseDsl for T {}
@@ -0,0 +1,211 @@
+extern crate libsqlite3_sys as ffi;
+
+use std::ffi::CString;
+
+use super::raw::RawConnection;
+use crate::deserialize::FromSql;
+use crate::result::QueryResult;
+use crate::sql_types::SqlType;
+use crate::sqlite::{Sqlite, SqliteValue};
+
+/// A single step of an aggregate SQL function.
+///
+/// Implementors hold whatever running state the aggregate needs (a sum, a
+/// running count, a concatenation buffer, ...); [`Self::default()`] is the
+/// seed value for a fresh group.
+pub trait SqliteAggregateFunction<Args>: Default {
+    /// The Rust type returned by `finalize`, handed back to SQLite through
+    /// [`SqliteResultValue`].
+    type Output;
+
+    /// Called once per row in the current group.
+    fn step(&mut self, args: Args);
+
+    /// Called once all rows in the group have been seen, consuming the
+    /// accumulator and producing the aggregate's result.
+    fn finalize(self) -> Self::Output;
+}
+
+/// Deserializes the raw `sqlite3_value` arguments SQLite hands a
+/// registered function into the tuple of Rust values its closure (or
+/// [`SqliteAggregateFunction::step`]) actually expects.
+///
+/// `ArgsSqlType` is the tuple of diesel SQL types (e.g. `(Integer, Text)`)
+/// each `Rust` value is deserialized from, exactly as callers of `.load()`
+/// pick the `Queryable<ST, DB>` impl to use by supplying `ST`; `FromSql` is
+/// keyed by SQL type, not by the Rust type alone, so that type can't be
+/// inferred from `Self` the way the hard-coded-arity version assumed.
+///
+/// Implemented for tuples `(T1,)`, `(T1, T2)`, `(T1, T2, T3)` so that the
+/// function's arity is derived from `Args`/`ArgsSqlType` themselves, rather
+/// than being hard-coded by the caller.
+#[doc(hidden)]
+pub trait SqliteFunctionArgs<ArgsSqlType>: Sized {
+    /// The number of SQL arguments this function takes.
+    const ARITY: i32;
+
+    /// Deserializes each of `raw_args` through `FromSql`, in order.
+    fn from_raw_args(raw_args: &[*mut ffi::sqlite3_value]) -> QueryResult<Self>;
+}
+
+macro_rules! sqlite_function_args_impl {
+    ($arity:expr, $($T:ident, $ST:ident => $idx:tt),+) => {
+        impl<$($T, $ST),+> SqliteFunctionArgs<($($ST,)+)> for ($($T,)+)
+        where
+            $($ST: SqlType,)+
+            $($T: FromSql<$ST, Sqlite> + 'static,)+
+        {
+            const ARITY: i32 = $arity;
+
+            fn from_raw_args(raw_args: &[*mut ffi::sqlite3_value]) -> QueryResult<Self> {
+                Ok(($({
+                    let value = SqliteValue::from_raw_value(raw_args[$idx])?;
+                    $T::from_sql(value)?
+                },)+))
+            }
+        }
+    };
+}
+
+sqlite_function_args_impl!(1, T1, ST1 => 0);
+sqlite_function_args_impl!(2, T1, ST1 => 0, T2, ST2 => 1);
+sqlite_function_args_impl!(3, T1, ST1 => 0, T2, ST2 => 1, T3, ST3 => 2);
+
+/// Bridges a scalar or aggregate function's Rust return value to the
+/// handful of fundamental storage classes SQLite understands, so it can be
+/// handed back via `sqlite3_result_*`.
+#[doc(hidden)]
+pub trait SqliteResultValue {
+    /// Writes `self` as the result of the function call currently running
+    /// under `ctx`.
+    fn set_sqlite_result(self, ctx: *mut ffi::sqlite3_context);
+}
+
+impl SqliteResultValue for i32 {
+    fn set_sqlite_result(self, ctx: *mut ffi::sqlite3_context) {
+        unsafe { ffi::sqlite3_result_int(ctx, self) }
+    }
+}
+
+impl SqliteResultValue for i64 {
+    fn set_sqlite_result(self, ctx: *mut ffi::sqlite3_context) {
+        unsafe { ffi::sqlite3_result_int64(ctx, self) }
+    }
+}
+
+impl SqliteResultValue for f64 {
+    fn set_sqlite_result(self, ctx: *mut ffi::sqlite3_context) {
+        unsafe { ffi::sqlite3_result_double(ctx, self) }
+    }
+}
+
+impl SqliteResultValue for bool {
+    fn set_sqlite_result(self, ctx: *mut ffi::sqlite3_context) {
+        (self as i32).set_sqlite_result(ctx)
+    }
+}
+
+impl SqliteResultValue for String {
+    fn set_sqlite_result(self, ctx: *mut ffi::sqlite3_context) {
+        unsafe {
+            ffi::sqlite3_result_text(
+                ctx,
+                self.as_ptr() as *const _,
+                self.len() as i32,
+                ffi::SQLITE_TRANSIENT(),
+            )
+        }
+    }
+}
+
+impl SqliteResultValue for Vec<u8> {
+    fn set_sqlite_result(self, ctx: *mut ffi::sqlite3_context) {
+        unsafe {
+            ffi::sqlite3_result_blob(
+                ctx,
+                self.as_ptr() as *const _,
+                self.len() as i32,
+                ffi::SQLITE_TRANSIENT(),
+            )
+        }
+    }
+}
+
+impl<T: SqliteResultValue> SqliteResultValue for Option<T> {
+    fn set_sqlite_result(self, ctx: *mut ffi::sqlite3_context) {
+        match self {
+            Some(value) => value.set_sqlite_result(ctx),
+            None => unsafe { ffi::sqlite3_result_null(ctx) },
+        }
+    }
+}
+
+fn report_error(ctx: *mut ffi::sqlite3_context, error: &crate::result::Error) {
+    let msg = error.to_string();
+    let msg = CString::new(msg).unwrap_or_else(|_| CString::new("error").unwrap());
+    unsafe { ffi::sqlite3_result_error(ctx, msg.as_ptr(), -1) }
+}
+
+/// Registers a scalar SQL function that can then be called from any query
+/// built through the DSL, or from raw SQL, for the lifetime of
+/// `conn`.
+///
+/// `f` receives its arguments already deserialized through `FromSql`, and
+/// its return value is serialized back through [`SqliteResultValue`]
+/// before being handed to SQLite.
+pub(crate) fn register<ArgsSqlType, Args, Ret, F>(
+    conn: &RawConnection,
+    fn_name: &str,
+    deterministic: bool,
+    mut f: F,
+) -> QueryResult<()>
+where
+    F: FnMut(Args) -> Ret + Send + 'static,
+    Args: SqliteFunctionArgs<ArgsSqlType> + 'static,
+    Ret: SqliteResultValue,
+{
+    conn.register_scalar_function(fn_name, Args::ARITY, deterministic, move |ctx, raw_args| {
+        match Args::from_raw_args(raw_args) {
+            Ok(args) => f(args).set_sqlite_result(ctx),
+            Err(e) => report_error(ctx, &e),
+        }
+    })
+}
+
+/// Registers an aggregate SQL function for the lifetime of `conn`.
+///
+/// This is the multi-row counterpart to [`register`]: `A` accumulates
+/// state across every row in a `GROUP BY` group via
+/// [`SqliteAggregateFunction::step`], then produces the final value via
+/// [`SqliteAggregateFunction::finalize`].
+pub(crate) fn register_aggregate<ArgsSqlType, Args, A>(
+    conn: &RawConnection,
+    fn_name: &str,
+) -> QueryResult<()>
+where
+    A: SqliteAggregateFunction<Args> + 'static + Send,
+    Args: SqliteFunctionArgs<ArgsSqlType> + 'static,
+    A::Output: SqliteResultValue,
+{
+    conn.register_aggregate_function::<A, _, _>(
+        fn_name,
+        Args::ARITY,
+        |ctx, raw_args| {
+            let mut state = unsafe { super::raw::aggregate_state::<A>(ctx) };
+            match Args::from_raw_args(raw_args) {
+                Ok(args) => state.get_or_insert_with(A::default).step(args),
+                Err(e) => report_error(ctx, &e),
+            }
+        },
+        |ctx| {
+            let mut state = unsafe { super::raw::aggregate_state::<A>(ctx) };
+            if let Some(state) = state.take() {
+                state.finalize().set_sqlite_result(ctx);
+            } else {
+                // The group was empty, so no accumulator was created;
+                // the result is that of finalizing a fresh `A::default()`.
+                A::default().finalize().set_sqlite_result(ctx);
+            }
+        },
+    )
+}
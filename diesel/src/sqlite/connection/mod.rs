@@ -0,0 +1,122 @@
+mod blob;
+mod functions;
+mod options;
+mod raw;
+
+pub use self::blob::Blob;
+pub use self::options::{JournalMode, SqliteConnectionOptions, Synchronous};
+#[cfg(feature = "r2d2")]
+pub use self::options::ConnectionOptionsCustomizer;
+
+use self::functions::{SqliteAggregateFunction, SqliteFunctionArgs, SqliteResultValue};
+use self::raw::RawConnection;
+use crate::connection::change_hooks::{ConnectionChangeHooks, Operation};
+use crate::connection::{Connection, ConnectionResult, SimpleConnection};
+use crate::result::QueryResult;
+
+/// Connections for the SQLite backend.
+///
+/// Unlike other backends, "connecting" to SQLite is not done over the
+/// network; `establish` takes a filesystem path (or `:memory:`).
+pub struct SqliteConnection {
+    raw_connection: RawConnection,
+}
+
+impl SimpleConnection for SqliteConnection {
+    fn batch_execute(&mut self, query: &str) -> QueryResult<()> {
+        self.raw_connection.batch_execute(query)
+    }
+}
+
+impl Connection for SqliteConnection {
+    type Backend = Sqlite;
+
+    fn establish(database_url: &str) -> ConnectionResult<Self> {
+        let raw_connection = RawConnection::establish(database_url)?;
+        Ok(SqliteConnection { raw_connection })
+    }
+}
+
+impl SqliteConnection {
+    /// Registers a scalar SQL function that can then be used in queries
+    /// built through the DSL, as well as in raw SQL.
+    ///
+    /// `deterministic` should be `true` when the function always returns
+    /// the same output for the same input; SQLite can then use it when
+    /// building an index.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel::prelude::*;
+    /// # use diesel::sql_types::Integer;
+    /// # fn main() -> QueryResult<()> {
+    /// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    /// conn.register_sql_function::<(Integer,), (i32,), i32, _>("double_it", true, |(x,)| x * 2)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_sql_function<ArgsSqlType, Args, Ret, F>(
+        &mut self,
+        fn_name: &str,
+        deterministic: bool,
+        f: F,
+    ) -> QueryResult<()>
+    where
+        F: FnMut(Args) -> Ret + Send + 'static,
+        Args: SqliteFunctionArgs<ArgsSqlType> + 'static,
+        Ret: SqliteResultValue,
+    {
+        functions::register(&self.raw_connection, fn_name, deterministic, f)
+    }
+
+    /// Registers an aggregate SQL function, implemented by the
+    /// [`SqliteAggregateFunction`] trait, that can then be used in queries
+    /// built through the DSL, as well as in raw SQL (e.g. inside a
+    /// `GROUP BY`).
+    pub fn register_aggregate_function<ArgsSqlType, Args, A>(
+        &mut self,
+        fn_name: &str,
+    ) -> QueryResult<()>
+    where
+        A: SqliteAggregateFunction<Args> + 'static + Send,
+        Args: SqliteFunctionArgs<ArgsSqlType> + 'static,
+        A::Output: SqliteResultValue,
+    {
+        functions::register_aggregate::<ArgsSqlType, Args, A>(&self.raw_connection, fn_name)
+    }
+
+    /// Opens an incremental I/O handle onto a single BLOB value, identified
+    /// by `table`, `column`, and `rowid`, without loading it into memory.
+    ///
+    /// The returned [`Blob`] implements [`std::io::Read`],
+    /// [`std::io::Write`] (writes return an error if `readonly` is `true`),
+    /// and [`std::io::Seek`], all bounded by the BLOB's current length —
+    /// incremental I/O cannot grow or shrink the value.
+    pub fn blob_open(
+        &mut self,
+        table: &str,
+        column: &str,
+        rowid: i64,
+        readonly: bool,
+    ) -> QueryResult<Blob> {
+        let handle = self
+            .raw_connection
+            .open_blob("main", table, column, rowid, readonly)?;
+        Ok(Blob::new(handle))
+    }
+}
+
+impl ConnectionChangeHooks for SqliteConnection {
+    fn update_hook(&mut self, callback: Option<Box<dyn FnMut(Operation, &str, i64) + Send>>) {
+        self.raw_connection.set_update_hook(callback);
+    }
+
+    fn commit_hook(&mut self, callback: Option<Box<dyn FnMut() -> bool + Send>>) {
+        self.raw_connection.set_commit_hook(callback);
+    }
+
+    fn rollback_hook(&mut self, callback: Option<Box<dyn FnMut() + Send>>) {
+        self.raw_connection.set_rollback_hook(callback);
+    }
+}
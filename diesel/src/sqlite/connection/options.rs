@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use super::SqliteConnection;
+use crate::connection::{Connection, ConnectionResult, SimpleConnection};
+
+/// Journal mode for a SQLite connection, set via
+/// [`SqliteConnectionOptions::journal_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalMode {
+    /// The default rollback journal.
+    Delete,
+    /// Write-ahead logging, which allows readers and a writer to proceed
+    /// concurrently.
+    Wal,
+}
+
+impl JournalMode {
+    fn pragma_value(self) -> &'static str {
+        match self {
+            JournalMode::Delete => "DELETE",
+            JournalMode::Wal => "WAL",
+        }
+    }
+}
+
+/// How aggressively SQLite flushes to disk, set via
+/// [`SqliteConnectionOptions::synchronous`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Synchronous {
+    /// Do not wait for data to reach disk.
+    Off,
+    /// Sync at critical moments; safe against application crashes, though
+    /// not against OS crashes or power loss.
+    Normal,
+    /// Sync whenever SQLite thinks it necessary; safest, slowest.
+    Full,
+}
+
+impl Synchronous {
+    fn pragma_value(self) -> &'static str {
+        match self {
+            Synchronous::Off => "OFF",
+            Synchronous::Normal => "NORMAL",
+            Synchronous::Full => "FULL",
+        }
+    }
+}
+
+/// Connection-time options applied right after opening a
+/// [`SqliteConnection`], via [`SqliteConnection::establish_with_options`].
+///
+/// Every option here corresponds to a `PRAGMA` that users previously had to
+/// issue by hand with [`SimpleConnection::batch_execute`] immediately after
+/// `establish`; collecting them here means they're applied atomically, in a
+/// known order, and can be reapplied consistently by a connection pool.
+#[derive(Debug, Clone, Default)]
+pub struct SqliteConnectionOptions {
+    enforce_foreign_keys: Option<bool>,
+    busy_timeout: Option<Duration>,
+    journal_mode: Option<JournalMode>,
+    synchronous: Option<Synchronous>,
+}
+
+impl SqliteConnectionOptions {
+    /// Creates an empty set of options; each unset option leaves SQLite's
+    /// own default in place.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables or disables `PRAGMA foreign_keys`.
+    pub fn enforce_foreign_keys(mut self, enforce: bool) -> Self {
+        self.enforce_foreign_keys = Some(enforce);
+        self
+    }
+
+    /// Installs a busy handler so that, for up to `timeout`, a connection
+    /// that finds the database locked retries instead of immediately
+    /// failing with `SQLITE_BUSY`. Equivalent to `PRAGMA busy_timeout`.
+    pub fn busy_timeout(mut self, timeout: Duration) -> Self {
+        self.busy_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets `PRAGMA journal_mode`.
+    pub fn journal_mode(mut self, mode: JournalMode) -> Self {
+        self.journal_mode = Some(mode);
+        self
+    }
+
+    /// Sets `PRAGMA synchronous`.
+    pub fn synchronous(mut self, level: Synchronous) -> Self {
+        self.synchronous = Some(level);
+        self
+    }
+
+    pub(super) fn apply_to(&self, conn: &mut SqliteConnection) -> ConnectionResult<()> {
+        if let Some(enforce) = self.enforce_foreign_keys {
+            let value = if enforce { "ON" } else { "OFF" };
+            conn.batch_execute(&format!("PRAGMA foreign_keys = {value}"))?;
+        }
+        if let Some(timeout) = self.busy_timeout {
+            conn.batch_execute(&format!(
+                "PRAGMA busy_timeout = {}",
+                timeout.as_millis()
+            ))?;
+        }
+        if let Some(mode) = self.journal_mode {
+            conn.batch_execute(&format!("PRAGMA journal_mode = {}", mode.pragma_value()))?;
+        }
+        if let Some(level) = self.synchronous {
+            conn.batch_execute(&format!("PRAGMA synchronous = {}", level.pragma_value()))?;
+        }
+        Ok(())
+    }
+}
+
+impl SqliteConnection {
+    /// Establishes a connection and applies `options` to it atomically
+    /// before returning it, so the connection is never observed in a
+    /// partially-configured state.
+    pub fn establish_with_options(
+        database_url: &str,
+        options: &SqliteConnectionOptions,
+    ) -> ConnectionResult<Self> {
+        let mut conn = Self::establish(database_url)?;
+        options.apply_to(&mut conn)?;
+        Ok(conn)
+    }
+}
+
+#[cfg(feature = "r2d2")]
+mod r2d2_customizer {
+    use super::SqliteConnectionOptions;
+    use crate::r2d2::{CustomizeConnection, Error as R2D2Error};
+    use crate::sqlite::SqliteConnection;
+
+    /// Applies a fixed [`SqliteConnectionOptions`] to every connection
+    /// handed out by an r2d2 pool, so pooled connections end up with the
+    /// same PRAGMAs as one established directly through
+    /// [`SqliteConnection::establish_with_options`].
+    #[derive(Debug, Clone)]
+    pub struct ConnectionOptionsCustomizer(pub SqliteConnectionOptions);
+
+    impl CustomizeConnection<SqliteConnection, R2D2Error> for ConnectionOptionsCustomizer {
+        fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), R2D2Error> {
+            self.0
+                .apply_to(conn)
+                .map_err(R2D2Error::ConnectionError)
+        }
+    }
+}
+
+#[cfg(feature = "r2d2")]
+pub use self::r2d2_customizer::ConnectionOptionsCustomizer;
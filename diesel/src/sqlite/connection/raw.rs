@@ -0,0 +1,381 @@
+extern crate libsqlite3_sys as ffi;
+
+use std::ffi::CString;
+use std::os::raw as libc;
+use std::ptr::NonNull;
+
+use crate::connection::change_hooks::Operation;
+use crate::result::{ConnectionError, ConnectionResult, QueryResult};
+
+/// Thin wrapper around the raw `sqlite3*` handle.
+///
+/// Everything that needs to call into `libsqlite3-sys` directly (statement
+/// preparation, function registration, update/commit hooks, incremental
+/// BLOB I/O, ...) goes through here so that `SqliteConnection` itself can
+/// stay focused on the `Connection` trait implementation.
+pub(super) struct RawConnection {
+    pub(super) internal_connection: NonNull<ffi::sqlite3>,
+    // Kept alive for as long as a hook of the corresponding kind is
+    // registered. Each setter drops the previous closure and then points
+    // SQLite at the new one (or clears the hook), and `Drop` detaches all
+    // hooks before closing, so SQLite never invokes a freed closure.
+    update_hook: Option<Box<Box<dyn FnMut(Operation, &str, i64) + Send>>>,
+    commit_hook: Option<Box<Box<dyn FnMut() -> bool + Send>>>,
+    rollback_hook: Option<Box<Box<dyn FnMut() + Send>>>,
+}
+
+impl RawConnection {
+    pub(super) fn establish(database_url: &str) -> ConnectionResult<Self> {
+        let mut conn_pointer = std::ptr::null_mut();
+        let database_url = CString::new(database_url)?;
+        let establish_result = unsafe {
+            ffi::sqlite3_open(database_url.as_ptr(), &mut conn_pointer as *mut *mut _)
+        };
+        match establish_result {
+            ffi::SQLITE_OK => {
+                let conn_pointer = unsafe { NonNull::new_unchecked(conn_pointer) };
+                Ok(RawConnection {
+                    internal_connection: conn_pointer,
+                    update_hook: None,
+                    commit_hook: None,
+                    rollback_hook: None,
+                })
+            }
+            _ => Err(ConnectionError::BadConnection(String::from(
+                "Unable to establish SQLite connection",
+            ))),
+        }
+    }
+
+    /// Registers a scalar SQL function, as used by
+    /// [`SqliteConnection::register_sql_function`].
+    ///
+    /// `callback` must be `'static` + `Send` because SQLite may invoke it from internal
+    /// contexts well after the call that registered it has returned; it is
+    /// kept alive (boxed, on the heap) for the lifetime of the connection
+    /// and dropped via the `xDestroy` callback passed to
+    /// `sqlite3_create_function_v2`.
+    pub(super) fn register_scalar_function<F>(
+        &self,
+        fn_name: &str,
+        num_args: i32,
+        deterministic: bool,
+        callback: F,
+    ) -> QueryResult<()>
+    where
+        F: FnMut(*mut ffi::sqlite3_context, &[*mut ffi::sqlite3_value]) + Send + 'static,
+    {
+        let fn_name = CString::new(fn_name)?;
+        let mut flags = ffi::SQLITE_UTF8;
+        if deterministic {
+            flags |= ffi::SQLITE_DETERMINISTIC;
+        }
+
+        let callback = Box::into_raw(Box::new(callback));
+
+        let result = unsafe {
+            ffi::sqlite3_create_function_v2(
+                self.internal_connection.as_ptr(),
+                fn_name.as_ptr(),
+                num_args,
+                flags,
+                callback as *mut libc::c_void,
+                Some(run_scalar_function::<F>),
+                None,
+                None,
+                Some(destroy_boxed::<F>),
+            )
+        };
+
+        Self::check_ffi_result(result)
+    }
+
+    /// Registers an aggregate SQL function, as used by
+    /// [`SqliteConnection::register_aggregate_function`].
+    ///
+    /// `step` is invoked once per row in a group (with the per-group
+    /// accumulator reachable through [`aggregate_state`]), and `finalize`
+    /// once the group is complete.
+    pub(super) fn register_aggregate_function<A, Step, Final>(
+        &self,
+        fn_name: &str,
+        num_args: i32,
+        step: Step,
+        finalize: Final,
+    ) -> QueryResult<()>
+    where
+        A: Default + Send + 'static,
+        Step: FnMut(*mut ffi::sqlite3_context, &[*mut ffi::sqlite3_value]) + Send + 'static,
+        Final: FnMut(*mut ffi::sqlite3_context) + Send + 'static,
+    {
+        let fn_name = CString::new(fn_name)?;
+        let callbacks = Box::into_raw(Box::new((step, finalize)));
+
+        let result = unsafe {
+            ffi::sqlite3_create_function_v2(
+                self.internal_connection.as_ptr(),
+                fn_name.as_ptr(),
+                num_args,
+                ffi::SQLITE_UTF8,
+                callbacks as *mut libc::c_void,
+                None,
+                Some(run_aggregate_step::<Step, Final>),
+                Some(run_aggregate_final::<Step, Final>),
+                Some(destroy_boxed::<(Step, Final)>),
+            )
+        };
+
+        Self::check_ffi_result(result)
+    }
+
+    /// Registers (or clears) the `update_hook`. See
+    /// [`ConnectionChangeHooks::update_hook`](crate::connection::change_hooks::ConnectionChangeHooks::update_hook).
+    pub(super) fn set_update_hook(
+        &mut self,
+        callback: Option<Box<dyn FnMut(Operation, &str, i64) + Send>>,
+    ) {
+        self.update_hook = callback.map(Box::new);
+        let user_data = self
+            .update_hook
+            .as_deref()
+            .map_or(std::ptr::null_mut(), |f| {
+                f as *const _ as *mut libc::c_void
+            });
+        unsafe {
+            ffi::sqlite3_update_hook(
+                self.internal_connection.as_ptr(),
+                self.update_hook.as_ref().map(|_| run_update_hook as _),
+                user_data,
+            );
+        }
+    }
+
+    /// Registers (or clears) the `commit_hook`. See
+    /// [`ConnectionChangeHooks::commit_hook`](crate::connection::change_hooks::ConnectionChangeHooks::commit_hook).
+    pub(super) fn set_commit_hook(&mut self, callback: Option<Box<dyn FnMut() -> bool + Send>>) {
+        self.commit_hook = callback.map(Box::new);
+        let user_data = self
+            .commit_hook
+            .as_deref()
+            .map_or(std::ptr::null_mut(), |f| {
+                f as *const _ as *mut libc::c_void
+            });
+        unsafe {
+            ffi::sqlite3_commit_hook(
+                self.internal_connection.as_ptr(),
+                self.commit_hook.as_ref().map(|_| run_commit_hook as _),
+                user_data,
+            );
+        }
+    }
+
+    /// Registers (or clears) the `rollback_hook`. See
+    /// [`ConnectionChangeHooks::rollback_hook`](crate::connection::change_hooks::ConnectionChangeHooks::rollback_hook).
+    pub(super) fn set_rollback_hook(&mut self, callback: Option<Box<dyn FnMut() + Send>>) {
+        self.rollback_hook = callback.map(Box::new);
+        let user_data = self
+            .rollback_hook
+            .as_deref()
+            .map_or(std::ptr::null_mut(), |f| {
+                f as *const _ as *mut libc::c_void
+            });
+        unsafe {
+            ffi::sqlite3_rollback_hook(
+                self.internal_connection.as_ptr(),
+                self.rollback_hook.as_ref().map(|_| run_rollback_hook as _),
+                user_data,
+            );
+        }
+    }
+
+    /// Opens an incremental I/O handle onto a single BLOB value, as used by
+    /// [`SqliteConnection::blob_open`].
+    pub(super) fn open_blob(
+        &self,
+        db_name: &str,
+        table: &str,
+        column: &str,
+        rowid: i64,
+        readonly: bool,
+    ) -> QueryResult<NonNull<ffi::sqlite3_blob>> {
+        let db_name = CString::new(db_name)?;
+        let table = CString::new(table)?;
+        let column = CString::new(column)?;
+        let mut blob_ptr = std::ptr::null_mut();
+
+        let result = unsafe {
+            ffi::sqlite3_blob_open(
+                self.internal_connection.as_ptr(),
+                db_name.as_ptr(),
+                table.as_ptr(),
+                column.as_ptr(),
+                rowid,
+                if readonly { 0 } else { 1 },
+                &mut blob_ptr,
+            )
+        };
+
+        Self::check_ffi_result(result)?;
+        NonNull::new(blob_ptr).ok_or_else(|| {
+            crate::result::Error::QueryBuilderError("sqlite3_blob_open returned a null handle".into())
+        })
+    }
+
+    pub(super) fn batch_execute(&self, query: &str) -> QueryResult<()> {
+        let query = CString::new(query)?;
+        let result = unsafe {
+            ffi::sqlite3_exec(
+                self.internal_connection.as_ptr(),
+                query.as_ptr(),
+                None,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+        };
+        Self::check_ffi_result(result)
+    }
+
+    fn check_ffi_result(result: libc::c_int) -> QueryResult<()> {
+        if result == ffi::SQLITE_OK {
+            Ok(())
+        } else {
+            Err(crate::result::Error::QueryBuilderError(
+                format!("SQLite call failed (error code {result})").into(),
+            ))
+        }
+    }
+}
+
+impl Drop for RawConnection {
+    fn drop(&mut self) {
+        use std::os::raw::c_int;
+        // Detach any registered hooks before closing, so SQLite never holds
+        // a pointer into memory we are about to free.
+        self.set_update_hook(None);
+        self.set_commit_hook(None);
+        self.set_rollback_hook(None);
+        unsafe {
+            let close_result = ffi::sqlite3_close(self.internal_connection.as_ptr());
+            assert_eq!(close_result as c_int, ffi::SQLITE_OK, "Error closing SQLite connection");
+        }
+    }
+}
+
+unsafe extern "C" fn run_update_hook(
+    data: *mut libc::c_void,
+    operation: libc::c_int,
+    _db_name: *const libc::c_char,
+    table_name: *const libc::c_char,
+    rowid: i64,
+) {
+    let callback = &mut *(data as *mut Box<dyn FnMut(Operation, &str, i64) + Send>);
+    let operation = match operation {
+        ffi::SQLITE_INSERT => Operation::Insert,
+        ffi::SQLITE_DELETE => Operation::Delete,
+        _ => Operation::Update,
+    };
+    let table_name = std::ffi::CStr::from_ptr(table_name).to_string_lossy();
+    callback(operation, &table_name, rowid);
+}
+
+unsafe extern "C" fn run_commit_hook(data: *mut libc::c_void) -> libc::c_int {
+    let callback = &mut *(data as *mut Box<dyn FnMut() -> bool + Send>);
+    libc::c_int::from(callback())
+}
+
+unsafe extern "C" fn run_rollback_hook(data: *mut libc::c_void) {
+    let callback = &mut *(data as *mut Box<dyn FnMut() + Send>);
+    callback();
+}
+
+unsafe extern "C" fn run_scalar_function<F>(
+    ctx: *mut ffi::sqlite3_context,
+    num_args: libc::c_int,
+    value_ptr: *mut *mut ffi::sqlite3_value,
+) where
+    F: FnMut(*mut ffi::sqlite3_context, &[*mut ffi::sqlite3_value]) + Send + 'static,
+{
+    let args = std::slice::from_raw_parts(value_ptr, num_args as usize);
+    let data = ffi::sqlite3_user_data(ctx) as *mut F;
+    (*data)(ctx, args);
+}
+
+/// A handle to the per-group accumulator slot for the aggregate currently
+/// running under `ctx`, as returned by [`aggregate_state`].
+///
+/// `sqlite3_aggregate_context` hands back a zeroed, fixed-size buffer, but
+/// zero-initializing an arbitrary `Option<A>` is not guaranteed by Rust to
+/// produce `None` — so rather than storing `A` inline, the buffer holds a
+/// single pointer-sized slot that is either null (no accumulator yet for
+/// this group) or a `Box<A>` allocated on first use. All-zero bits are
+/// always a valid null pointer, which is a guarantee `Option<A>`'s layout
+/// does not make for an arbitrary `A`.
+///
+/// If a query aborts mid-group (e.g. is dropped without running to
+/// completion) SQLite frees the context buffer directly, without calling
+/// `finalize`, so a still-boxed accumulator leaks; this mirrors the same
+/// risk inherent to any heap allocation threaded through raw SQLite
+/// callbacks and is not specific to this wrapper.
+pub(super) struct AggregateState<'a, A> {
+    slot: *mut *mut A,
+    _marker: std::marker::PhantomData<&'a mut A>,
+}
+
+impl<'a, A> AggregateState<'a, A> {
+    /// Returns the accumulator for the current group, initializing it with
+    /// `default` if this is the first row seen for the group.
+    pub(super) fn get_or_insert_with(&mut self, default: impl FnOnce() -> A) -> &mut A {
+        unsafe {
+            if (*self.slot).is_null() {
+                *self.slot = Box::into_raw(Box::new(default()));
+            }
+            &mut **self.slot
+        }
+    }
+
+    /// Takes ownership of the accumulator for the current group, if one was
+    /// ever initialized (i.e. `step` ran at least once).
+    pub(super) fn take(&mut self) -> Option<A> {
+        unsafe {
+            let ptr = std::mem::replace(&mut *self.slot, std::ptr::null_mut());
+            (!ptr.is_null()).then(|| *Box::from_raw(ptr))
+        }
+    }
+}
+
+/// Returns the per-group accumulator slot for the aggregate currently
+/// running under `ctx`. See [`AggregateState`].
+pub(super) unsafe fn aggregate_state<'a, A>(ctx: *mut ffi::sqlite3_context) -> AggregateState<'a, A> {
+    let slot =
+        ffi::sqlite3_aggregate_context(ctx, std::mem::size_of::<*mut A>() as libc::c_int) as *mut *mut A;
+    AggregateState {
+        slot,
+        _marker: std::marker::PhantomData,
+    }
+}
+
+unsafe extern "C" fn run_aggregate_step<Step, Final>(
+    ctx: *mut ffi::sqlite3_context,
+    num_args: libc::c_int,
+    value_ptr: *mut *mut ffi::sqlite3_value,
+) where
+    Step: FnMut(*mut ffi::sqlite3_context, &[*mut ffi::sqlite3_value]) + Send + 'static,
+    Final: FnMut(*mut ffi::sqlite3_context) + Send + 'static,
+{
+    let args = std::slice::from_raw_parts(value_ptr, num_args as usize);
+    let callbacks = ffi::sqlite3_user_data(ctx) as *mut (Step, Final);
+    ((*callbacks).0)(ctx, args);
+}
+
+unsafe extern "C" fn run_aggregate_final<Step, Final>(ctx: *mut ffi::sqlite3_context)
+where
+    Step: FnMut(*mut ffi::sqlite3_context, &[*mut ffi::sqlite3_value]) + Send + 'static,
+    Final: FnMut(*mut ffi::sqlite3_context) + Send + 'static,
+{
+    let callbacks = ffi::sqlite3_user_data(ctx) as *mut (Step, Final);
+    ((*callbacks).1)(ctx);
+}
+
+unsafe extern "C" fn destroy_boxed<F>(data: *mut libc::c_void) {
+    drop(Box::from_raw(data as *mut F));
+}
@@ -0,0 +1,63 @@
+use crate::dsl;
+use crate::expression::grouped::Grouped;
+use crate::expression::operators::{Glob, NotGlob};
+use crate::expression::{AsExpression, Expression};
+use crate::expression_methods::text_expression_methods::TextOrNullableText;
+use crate::sql_types::SqlType;
+
+/// SQLite specific methods which are present on text expressions.
+#[cfg(feature = "sqlite")]
+pub trait SqliteTextExpressionMethods: Expression + Sized {
+    /// Returns a SQL `GLOB` expression
+    ///
+    /// Unlike `LIKE`, `GLOB` is case-sensitive and matches Unix-style
+    /// wildcards (`*`, `?`, `[...]`) rather than `%`/`_`. This is specific
+    /// to SQLite; other backends do not have a `GLOB` operator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # include!("../../doctest_setup.rs");
+    /// #
+    /// # fn main() {
+    /// #     run_test().unwrap();
+    /// # }
+    /// #
+    /// # fn run_test() -> QueryResult<()> {
+    /// #     use schema::users::dsl::*;
+    /// #     let mut connection = establish_connection();
+    /// #
+    /// let starts_with_s = users
+    ///     .select(name)
+    ///     .filter(name.glob("S*"))
+    ///     .load::<String>(&mut connection)?;
+    /// assert_eq!(vec!["Sean".to_string()], starts_with_s);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    fn glob<T>(self, pattern: T) -> dsl::Glob<Self, T>
+    where
+        Self::SqlType: SqlType,
+        T: AsExpression<Self::SqlType>,
+    {
+        Grouped(Glob::new(self, pattern.as_expression()))
+    }
+
+    /// Returns a SQL `NOT GLOB` expression
+    ///
+    /// See [`glob`](SqliteTextExpressionMethods::glob) for details.
+    fn not_glob<T>(self, pattern: T) -> dsl::NotGlob<Self, T>
+    where
+        Self::SqlType: SqlType,
+        T: AsExpression<Self::SqlType>,
+    {
+        Grouped(NotGlob::new(self, pattern.as_expression()))
+    }
+}
+
+impl<T> SqliteTextExpressionMethods for T
+where
+    T: Expression,
+    T::SqlType: TextOrNullableText,
+{
+}
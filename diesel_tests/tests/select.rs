@@ -223,6 +223,14 @@ table! {
     }
 }
 
+table! {
+    users_select_for_share {
+        id -> Integer,
+        name -> Text,
+        hair_color -> Nullable<Text>,
+    }
+}
+
 #[cfg(not(feature = "sqlite"))]
 #[test]
 fn select_for_update_locks_selected_rows() {
@@ -483,6 +491,72 @@ fn select_for_no_key_update_modifiers() {
     }
 }
 
+#[cfg(feature = "postgres")]
+#[test]
+fn select_for_share_and_for_key_share_do_not_block_each_other() {
+    use self::users_select_for_share::dsl::*;
+
+    let mut conn_1 = connection_without_transaction();
+    let mut conn_2 = connection();
+
+    conn_1
+        .execute("DROP TABLE IF EXISTS users_select_for_share")
+        .unwrap();
+    create_table(
+        "users_select_for_share",
+        (
+            integer("id").primary_key().auto_increment(),
+            string("name").not_null(),
+            string("hair_color"),
+        ),
+    )
+    .execute(&mut conn_1)
+    .unwrap();
+
+    conn_1
+        .execute("INSERT INTO users_select_for_share (name) VALUES ('Sean'), ('Tess')")
+        .unwrap();
+
+    conn_1.begin_test_transaction().unwrap();
+
+    // `FOR SHARE`/`FOR KEY SHARE` allow concurrent readers, so both
+    // connections should be able to take the lock on the same row without
+    // blocking each other.
+    let _sean_share = users_select_for_share
+        .order(name)
+        .for_share()
+        .first::<User>(&mut conn_1)
+        .unwrap();
+
+    let _sean_key_share = users_select_for_share
+        .order(name)
+        .for_key_share()
+        .first::<User>(&mut conn_2)
+        .unwrap();
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn select_for_update_of_named_table_runs_on_joined_query() {
+    use self::posts::dsl::*;
+    use self::users::dsl::*;
+
+    let mut connection = connection_with_sean_and_tess_in_users_table();
+    let sean = find_user_by_name("Sean", &mut connection);
+    insert_into(posts)
+        .values(sean.new_post("Hello", None))
+        .execute(&mut connection)
+        .unwrap();
+
+    let _locked = users
+        .inner_join(posts)
+        .select(users::all_columns())
+        .for_update()
+        .of(users)
+        .load::<User>(&mut connection)
+        .unwrap();
+}
+
 #[test]
 fn select_can_be_called_on_query_that_is_valid_subselect_but_invalid_query() {
     let mut connection = connection_with_sean_and_tess_in_users_table();